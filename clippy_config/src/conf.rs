@@ -0,0 +1,28 @@
+// NOTE: This is a trimmed excerpt of clippy_config's real `conf.rs`, showing only the two
+// fields `ifs_in_if_conditions` added to `Conf`. The full `define_Conf!` invocation configures
+// every other lint's toml options, none of which are reproduced here.
+
+use serde::Deserialize;
+
+/// The categories of "complex" condition expressions `clippy::ifs_in_if_conditions` looks for.
+/// Individual categories can be disabled via the `allowed-condition-exprs` clippy.toml option.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConditionExprKind {
+    NestedIf,
+    Block,
+    Match,
+    Loop,
+    Closure,
+}
+
+define_Conf! {
+    /// Lint: IFS_IN_IF_CONDITIONS.
+    ///
+    /// Which kinds of complex condition expressions `ifs_in_if_conditions` should ignore.
+    (allowed_condition_exprs: Vec<ConditionExprKind> = Vec::new()),
+    /// Lint: IFS_IN_IF_CONDITIONS.
+    ///
+    /// Whether `ifs_in_if_conditions` should still fire inside `#[test]`/`#[cfg(test)]` functions.
+    (lint_ifs_in_test_code: bool = false),
+}
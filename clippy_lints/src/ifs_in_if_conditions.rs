@@ -1,19 +1,29 @@
-use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_config::ConditionExprKind;
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_then};
 use clippy_utils::higher;
+use clippy_utils::source::{indent_of, snippet_with_applicability};
+use clippy_utils::{is_from_proc_macro, is_in_test_function};
 use hir::intravisit::walk_fn;
-use hir::{intravisit, Body, Expr, FnDecl};
+use hir::{intravisit, Body, Expr, ExprKind, FnDecl, HirId, LoopSource, Node};
 use intravisit::{walk_expr, FnKind, Visitor};
+use rustc_ast::Attribute;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::Applicability;
 use rustc_hir as hir;
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::hir::nested_filter;
 use rustc_middle::lint::in_external_macro;
-use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::def_id::LocalDefId;
-use rustc_span::Span;
+use rustc_span::{sym, Span};
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Checks for `if` expressions in the conditions of `if`/`else-if` expressions
+    /// Checks for hard-to-read expressions used in boolean condition positions: `if`/`else-if`
+    /// conditions, `while`/`while let` conditions, and `match` arm guards. Flags nested `if`s,
+    /// brace blocks, `match` expressions, `loop`/`while` expressions, and closures whose body is
+    /// a statement block passed as an argument inside the condition.
     ///
     /// ### Why is this bad?
     /// Doing so makes the code difficult to read.
@@ -34,9 +44,30 @@ declare_clippy_lint! {
     #[clippy::version = "1.74.0"]
     pub IFS_IN_IF_CONDITIONS,
     style,
-    "checks for usage of `if` expressions in the conditions of `if`/`else-if` expressions"
+    "checks for usage of complex expressions in boolean condition positions"
 }
-declare_lint_pass!(IfInIfCondition => [IFS_IN_IF_CONDITIONS]);
+
+pub struct IfInIfCondition {
+    allowed_condition_exprs: FxHashSet<ConditionExprKind>,
+    /// Whether the lint should still fire inside `#[test]`/`#[cfg(test)]` functions, set via the
+    /// `lint-ifs-in-test-code` clippy.toml option (`false` by default).
+    lint_ifs_in_test_code: bool,
+}
+
+impl IfInIfCondition {
+    pub fn new(allowed_condition_exprs: &[ConditionExprKind], lint_ifs_in_test_code: bool) -> Self {
+        Self {
+            allowed_condition_exprs: allowed_condition_exprs.iter().copied().collect(),
+            lint_ifs_in_test_code,
+        }
+    }
+
+    fn is_allowed(&self, kind: ConditionExprKind) -> bool {
+        self.allowed_condition_exprs.contains(&kind)
+    }
+}
+
+impl_lint_pass!(IfInIfCondition => [IFS_IN_IF_CONDITIONS]);
 
 impl<'tcx> LateLintPass<'tcx> for IfInIfCondition {
     fn check_fn(
@@ -52,19 +83,180 @@ impl<'tcx> LateLintPass<'tcx> for IfInIfCondition {
             return;
         }
 
-        let mut vis = IfVisitor::new(cx);
+        let fn_hir_id = cx.tcx.hir().local_def_id_to_hir_id(id);
+        if !self.lint_ifs_in_test_code
+            && (is_in_test_function(cx.tcx, fn_hir_id) || is_in_cfg_test(cx.tcx, fn_hir_id))
+        {
+            return;
+        }
+
+        let mut vis = IfVisitor::new(cx, self);
         walk_fn(&mut vis, kind, decl, body.id(), id);
     }
 }
 
+/// A boolean-condition site the lint can anchor a nested-expression diagnostic to: the
+/// `if`/`while`/`match` expression it came from, whether hoisting a `let` in front of it is semantically sound, and
+/// how to name it in diagnostic messages.
+#[derive(Clone, Copy)]
+struct ConditionSite<'tcx> {
+    /// The enclosing `if`/`while`/`match` expression, used to find the insertion point for a
+    /// hoisted `let` binding.
+    anchor: &'tcx Expr<'tcx>,
+    /// `false` for `while`/`while let` conditions (re-evaluated every iteration, so hoisting would
+    /// change behavior) and `match` guards (may reference the arm's pattern bindings, which
+    /// aren't in scope before the `match`).
+    hoistable: bool,
+    name: &'static str,
+}
+
 struct IfVisitor<'a, 'tcx> {
     cx: &'a LateContext<'tcx>,
+    pass: &'a IfInIfCondition,
     in_outer_if: bool,
+    site: Option<ConditionSite<'tcx>>,
 }
 
 impl<'a, 'tcx> IfVisitor<'a, 'tcx> {
-    fn new(cx: &'a LateContext<'tcx>) -> Self {
-        Self { cx, in_outer_if: true }
+    fn new(cx: &'a LateContext<'tcx>, pass: &'a IfInIfCondition) -> Self {
+        Self {
+            cx,
+            pass,
+            in_outer_if: true,
+            site: None,
+        }
+    }
+
+    /// Visits `condition` with `site` as the active condition context, restoring the previous
+    /// context afterwards.
+    ///
+    /// Dispatches through `self.visit_expr` (rather than walking `condition`'s children directly)
+    /// so that `condition` itself is checked too, not just expressions nested inside it — a
+    /// condition can consist *entirely* of a flagged expression, e.g. `if match x { .. } { }` or
+    /// `if { let y = f(); y } { }`.
+    fn visit_condition(&mut self, condition: &'tcx Expr<'tcx>, site: ConditionSite<'tcx>) {
+        let prev_site = self.site;
+        let prev_in_outer_if = self.in_outer_if;
+        self.site = Some(site);
+        self.in_outer_if = false;
+
+        self.visit_expr(condition);
+
+        self.site = prev_site;
+        self.in_outer_if = prev_in_outer_if;
+    }
+
+    /// Checks `expr` for the non-`if` complex condition expressions: blocks, `match`, `loop`,
+    /// `while`, and closures with a block body passed as a call argument.
+    fn check_condition_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+        if self.in_outer_if {
+            return;
+        }
+        let Some(site) = self.site else { return };
+
+        if let ExprKind::Call(_, args) | ExprKind::MethodCall(_, _, args, _) = expr.kind {
+            if !self.pass.is_allowed(ConditionExprKind::Closure) {
+                for arg in args {
+                    self.check_closure_arg(arg, site);
+                }
+            }
+            return;
+        }
+
+        match expr.kind {
+            ExprKind::Block(block, _) if !self.pass.is_allowed(ConditionExprKind::Block) => {
+                if let Some(tail) = block.expr
+                    && block.stmts.is_empty()
+                    && block.rules == hir::BlockCheckMode::DefaultBlock
+                    && !is_from_proc_macro(self.cx, expr)
+                {
+                    span_lint_and_then(
+                        self.cx,
+                        IFS_IN_IF_CONDITIONS,
+                        expr.span,
+                        format!("block in `{}` condition can be simplified", site.name),
+                        |diag| {
+                            let mut applicability = Applicability::MachineApplicable;
+                            let tail_snippet = snippet_with_applicability(self.cx, tail.span, "..", &mut applicability);
+                            diag.span_suggestion(expr.span, "remove the braces", tail_snippet, applicability);
+                        },
+                    );
+                } else {
+                    hoist_to_let(
+                        self.cx,
+                        site,
+                        expr,
+                        &format!("block in `{}` condition", site.name),
+                        &hoist_help("block"),
+                    );
+                }
+            },
+            ExprKind::Match(..) if !self.pass.is_allowed(ConditionExprKind::Match) => {
+                hoist_to_let(
+                    self.cx,
+                    site,
+                    expr,
+                    &format!("`match` expr in `{}` condition", site.name),
+                    &hoist_help("`match`"),
+                );
+            },
+            ExprKind::Loop(_, _, source, _) if !self.pass.is_allowed(ConditionExprKind::Loop) => {
+                let keyword = if matches!(source, LoopSource::While | LoopSource::WhileLet) {
+                    "while"
+                } else {
+                    "loop"
+                };
+                hoist_to_let(
+                    self.cx,
+                    site,
+                    expr,
+                    &format!("`{keyword}` expr in `{}` condition", site.name),
+                    &hoist_help(&format!("`{keyword}`")),
+                );
+            },
+            _ => {},
+        }
+    }
+
+    fn check_closure_arg(&self, arg: &'tcx Expr<'tcx>, site: ConditionSite<'tcx>) {
+        let ExprKind::Closure(closure) = arg.kind else {
+            return;
+        };
+        let body = self.cx.tcx.hir().body(closure.body);
+        let ExprKind::Block(block, _) = body.value.kind else {
+            return;
+        };
+
+        if is_from_proc_macro(self.cx, arg) {
+            return;
+        }
+
+        if let Some(tail) = block.expr
+            && block.stmts.is_empty()
+            && block.rules == hir::BlockCheckMode::DefaultBlock
+        {
+            span_lint_and_then(
+                self.cx,
+                IFS_IN_IF_CONDITIONS,
+                arg.span,
+                format!("closure with a block body passed as an argument inside a `{}` condition", site.name),
+                |diag| {
+                    let mut applicability = Applicability::MachineApplicable;
+                    let tail_snippet = snippet_with_applicability(self.cx, tail.span, "..", &mut applicability);
+                    diag.span_suggestion(body.value.span, "remove the braces", tail_snippet, applicability);
+                },
+            );
+        } else if !block.stmts.is_empty() {
+            span_lint_and_help(
+                self.cx,
+                IFS_IN_IF_CONDITIONS,
+                arg.span,
+                format!("closure with a block body passed as an argument inside a `{}` condition", site.name),
+                None,
+                "consider moving the closure's body out into a named function or a variable bound before the \
+                 condition",
+            );
+        }
     }
 }
 
@@ -72,38 +264,233 @@ impl<'a, 'tcx> Visitor<'tcx> for IfVisitor<'a, 'tcx> {
     type NestedFilter = nested_filter::OnlyBodies;
 
     fn visit_expr(&mut self, expr: &'tcx Expr<'_>) {
-        // Don't lint `expr`s in macros
+        // Don't lint `expr`s in macros. `is_from_proc_macro` re-tokenizes source and is checked
+        // lazily right before each diagnostic is emitted instead of here, since this is a
+        // per-node traversal gate hit for every expression in the function.
         if in_external_macro(self.cx.tcx.sess, expr.span) {
             return;
         }
 
+        // Save the context we were entered with and restore it on the way out, rather than
+        // unconditionally flipping back to `true`. `expr` may be one operand of several inside an
+        // active condition (e.g. the left side of a `&&`), and visiting it can itself flip
+        // `in_outer_if` back to `true` internally (entering an `if`'s `else` branch, say); without
+        // restoring afterwards that would leak into the *next* operand and stop it from being
+        // checked.
+        let prev_in_outer_if = self.in_outer_if;
+
         if let Some(higher::If { cond, then: _, r#else }) = higher::If::hir(expr) {
-            if !self.in_outer_if {
-                span_lint_and_help(
-                    self.cx,
-                    IFS_IN_IF_CONDITIONS,
-                    expr.span,
-                    "`if` expr in `if` condition",
-                    None,
-                    "consider assigning the result of the `if` to a variable and using the variable in the condition instead",
-                );
+            if !self.in_outer_if && !self.pass.is_allowed(ConditionExprKind::NestedIf) {
+                if let Some(site) = self.site {
+                    hoist_to_let(
+                        self.cx,
+                        site,
+                        expr,
+                        &format!("`if` expr in `{}` condition", site.name),
+                        &hoist_help("`if`"),
+                    );
+                }
             }
-            self.in_outer_if = false;
+            self.visit_condition(
+                cond,
+                ConditionSite {
+                    anchor: expr,
+                    hoistable: true,
+                    name: "if",
+                },
+            );
 
-            walk_expr(self, cond);
             if let Some(r#else) = r#else {
                 self.in_outer_if = true;
                 self.visit_expr(r#else);
             }
-        } else {
+        } else if let Some(higher::While { condition, .. }) = higher::While::hir(expr) {
+            self.check_condition_expr(expr);
+            self.visit_condition(
+                condition,
+                ConditionSite {
+                    anchor: expr,
+                    hoistable: false,
+                    name: "while",
+                },
+            );
+        } else if let Some(higher::WhileLet { let_expr, .. }) = higher::WhileLet::hir(expr) {
+            self.check_condition_expr(expr);
+            self.visit_condition(
+                let_expr,
+                ConditionSite {
+                    anchor: expr,
+                    hoistable: false,
+                    name: "while let",
+                },
+            );
+        } else if let ExprKind::Match(scrutinee, arms, _) = expr.kind {
+            self.check_condition_expr(expr);
             self.in_outer_if = true;
+            walk_expr(self, scrutinee);
+            for arm in arms {
+                if let Some(guard) = arm.guard {
+                    self.visit_condition(
+                        guard,
+                        ConditionSite {
+                            anchor: expr,
+                            hoistable: false,
+                            name: "match guard",
+                        },
+                    );
+                }
+                self.in_outer_if = true;
+                self.visit_expr(arm.body);
+            }
+        } else if let ExprKind::Closure(_) = expr.kind {
+            // A closure's body isn't part of the condition itself; block-bodied closures passed as
+            // call arguments are reported by `check_closure_arg` instead.
+            self.in_outer_if = true;
+            walk_expr(self, expr);
+        } else {
+            self.check_condition_expr(expr);
             walk_expr(self, expr);
         }
 
-        self.in_outer_if = true;
+        self.in_outer_if = prev_in_outer_if;
     }
 
     fn nested_visit_map(&mut self) -> Self::Map {
         self.cx.tcx.hir()
     }
 }
+
+/// Finds the statement that `expr` (or one of its ancestors) is the whole of, so that a `let`
+/// binding can be inserted immediately before it, along with whether that search had to climb
+/// past an enclosing `else if`. Returns `None` if `expr` sits in a position where prepending a
+/// statement isn't legal, e.g. as the RHS of a `let`, a function argument, or a match arm.
+///
+/// For an `else if` chain, `expr` (the outermost `if`) sits as the `else` branch of another `if`
+/// expression rather than directly in a `Stmt`/`Block`; in that case we climb past the enclosing
+/// `if`/`else if` chain to find *its* insertion point, so each hoisted condition still gets a
+/// binding placed right before the whole chain. Doing so moves evaluation of the flagged
+/// expression out of its original `else if` branch, which the caller must account for (see the
+/// `climbed_else_if` return value and its use in `hoist_to_let`).
+fn insertion_point<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> Option<(Span, bool)> {
+    let map = cx.tcx.hir();
+    let mut current = expr;
+    let mut climbed_else_if = false;
+    loop {
+        match map.get_parent(current.hir_id) {
+            Node::Stmt(stmt) => return Some((stmt.span.shrink_to_lo(), climbed_else_if)),
+            Node::Block(block) if block.expr.map_or(false, |tail| tail.hir_id == current.hir_id) => {
+                return Some((current.span.shrink_to_lo(), climbed_else_if));
+            },
+            Node::Expr(parent) if is_else_branch(parent, current.hir_id) => {
+                current = parent;
+                climbed_else_if = true;
+            },
+            _ => return None,
+        }
+    }
+}
+
+/// Whether `child` is the `else` branch of the `if`/`else if` expression `parent`.
+fn is_else_branch(parent: &Expr<'_>, child: HirId) -> bool {
+    matches!(higher::If::hir(parent), Some(higher::If { r#else: Some(e), .. }) if e.hir_id == child)
+}
+
+/// Whether `id` or one of its ancestor items is annotated `#[cfg(test)]`, e.g. a helper function
+/// defined inside a `#[cfg(test)] mod tests { .. }` block. `is_in_test_function` alone only
+/// catches functions directly annotated `#[test]`, not such helpers.
+fn is_in_cfg_test(tcx: TyCtxt<'_>, id: HirId) -> bool {
+    let hir = tcx.hir();
+    has_cfg_test_attr(hir.attrs(id))
+        || hir
+            .parent_iter(id)
+            .any(|(parent_id, _)| has_cfg_test_attr(hir.attrs(parent_id)))
+}
+
+fn has_cfg_test_attr(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.has_name(sym::cfg)
+            && attr
+                .meta_item_list()
+                .is_some_and(|items| items.iter().any(|item| item.has_name(sym::test)))
+    })
+}
+
+/// Generates an identifier that doesn't shadow anything already bound in the body enclosing
+/// `around`, starting from `n` and falling back to `n2`, `n3`, ... until a free one is found.
+fn fresh_ident<'tcx>(cx: &LateContext<'tcx>, around: HirId) -> String {
+    struct BindingCollector<'s>(&'s mut FxHashSet<String>);
+    impl<'s, 'tcx> Visitor<'tcx> for BindingCollector<'s> {
+        fn visit_pat(&mut self, pat: &'tcx hir::Pat<'tcx>) {
+            if let hir::PatKind::Binding(_, _, ident, _) = pat.kind {
+                self.0.insert(ident.to_string());
+            }
+            hir::intravisit::walk_pat(self, pat);
+        }
+    }
+
+    let map = cx.tcx.hir();
+    let mut used = FxHashSet::default();
+    if let Some(body_id) = map.maybe_body_owned_by(map.enclosing_body_owner(around)) {
+        let body = map.body(body_id);
+        BindingCollector(&mut used).visit_body(body);
+    }
+
+    if !used.contains("n") {
+        return "n".to_string();
+    }
+    (2..).map(|i| format!("n{i}")).find(|name| !used.contains(name)).unwrap()
+}
+
+/// The help text shown when a flagged `kind` (e.g. `` `if` `` or `block`) can't be machine-hoisted.
+fn hoist_help(kind: &str) -> String {
+    format!(
+        "consider assigning the result of the {kind} to a variable and using the variable in the condition instead"
+    )
+}
+
+/// Suggests hoisting `flagged` (found somewhere in `site`'s condition) into a `let` binding placed
+/// immediately before the statement containing `site.anchor`. Falls back to a help-only message
+/// when the site isn't `hoistable`, or the anchor is itself an expression operand where prepending
+/// a statement isn't legal.
+fn hoist_to_let<'tcx>(
+    cx: &LateContext<'tcx>,
+    site: ConditionSite<'tcx>,
+    flagged: &'tcx Expr<'tcx>,
+    message: &str,
+    help: &str,
+) {
+    if is_from_proc_macro(cx, flagged) {
+        return;
+    }
+
+    let insertion = site.hoistable.then(|| insertion_point(cx, site.anchor)).flatten();
+
+    let Some((insertion_span, climbed_else_if)) = insertion else {
+        span_lint_and_help(cx, IFS_IN_IF_CONDITIONS, flagged.span, message, None, help);
+        return;
+    };
+
+    span_lint_and_then(cx, IFS_IN_IF_CONDITIONS, flagged.span, message, |diag| {
+        let name = fresh_ident(cx, site.anchor.hir_id);
+        let indent = indent_of(cx, site.anchor.span).unwrap_or_default();
+        let mut applicability = Applicability::MachineApplicable;
+        let flagged_snippet = snippet_with_applicability(cx, flagged.span, "..", &mut applicability);
+
+        // For an `else if`, the insertion point sits before the *whole* `if`/`else if` chain, so
+        // applying this unconditionally would evaluate `flagged` even when an earlier arm of the
+        // chain is taken, changing behavior if evaluating it has side effects. Downgrade rather
+        // than have `--fix` apply it silently.
+        if climbed_else_if {
+            applicability = Applicability::MaybeIncorrect;
+        }
+
+        diag.multipart_suggestion(
+            "assign the result to a variable",
+            vec![
+                (insertion_span, format!("let {name} = {flagged_snippet};\n{}", " ".repeat(indent))),
+                (flagged.span, name),
+            ],
+            applicability,
+        );
+    });
+}
@@ -0,0 +1,14 @@
+// NOTE: This is a trimmed excerpt of clippy_lints' real `lib.rs`, showing only the wiring
+// `ifs_in_if_conditions` needs. The full crate registers several hundred lints here; that
+// registration table, its `mod` declarations, and its imports aren't reproduced.
+
+mod ifs_in_if_conditions;
+
+pub fn register_lints(store: &mut rustc_lint::LintStore, conf: &clippy_config::Conf) {
+    store.register_late_pass(move |_| {
+        Box::new(ifs_in_if_conditions::IfInIfCondition::new(
+            &conf.allowed_condition_exprs,
+            conf.lint_ifs_in_test_code,
+        ))
+    });
+}
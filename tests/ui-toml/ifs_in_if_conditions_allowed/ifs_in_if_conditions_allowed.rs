@@ -0,0 +1,29 @@
+//@compile-flags: --edition 2021
+#![warn(clippy::ifs_in_if_conditions)]
+#![allow(clippy::nonminimal_bool, unused)]
+
+fn takes_closure(f: impl Fn() -> bool) -> bool {
+    f()
+}
+
+fn main() {
+    let a = 10;
+
+    // `nested-if` is in `allowed-condition-exprs`, so this no longer gets flagged
+    if if a == 13 { 10 } else { 0 } > 5 {
+        println!("nested if, allowed");
+    }
+
+    // `closure` is also allowed
+    if takes_closure(|| {
+        let b = a + 1;
+        b > 5
+    }) {
+        println!("closure, allowed");
+    }
+
+    // `block` isn't in the allow-list, so this is still flagged
+    if { a > 5 } {
+        println!("block, still flagged");
+    }
+}
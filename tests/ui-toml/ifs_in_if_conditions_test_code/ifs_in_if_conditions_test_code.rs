@@ -0,0 +1,24 @@
+//@compile-flags: --test --edition 2021
+#![warn(clippy::ifs_in_if_conditions)]
+#![allow(clippy::nonminimal_bool, unused)]
+
+fn main() {}
+
+#[test]
+fn flagged_in_test_fn() {
+    let a = 10;
+    if { a > 5 } {
+        println!("flagged, lint-ifs-in-test-code is true");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // Not itself `#[test]`-annotated, but still inside a `#[cfg(test)]` module.
+    fn flagged_test_helper() {
+        let a = 10;
+        if { a > 5 } {
+            println!("flagged, lint-ifs-in-test-code is true");
+        }
+    }
+}
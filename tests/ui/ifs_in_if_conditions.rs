@@ -0,0 +1,125 @@
+#![warn(clippy::ifs_in_if_conditions)]
+#![allow(clippy::nonminimal_bool, unused)]
+
+fn takes_closure(f: impl Fn() -> bool) -> bool {
+    f()
+}
+
+fn takes_closure_arg(f: impl Fn(i32) -> bool) -> bool {
+    f(47)
+}
+
+// nested `if` in an `if` condition
+fn nested_if(a: i32) {
+    if if a == 13 { 10 } else { 0 } > 5 {
+        println!("nested if");
+    }
+}
+
+// nested `if` in an `else if` condition; the suggestion is `MaybeIncorrect` here (hoisting ahead
+// of the whole chain would evaluate the condition unconditionally), so it isn't auto-applied
+fn nested_if_else_if(a: i32) {
+    if a == 1 {
+    } else if if a == 13 { 10 } else { 0 } > 5 {
+        println!("nested if in else-if");
+    }
+}
+
+// nested `if` as the *second* operand of `&&`/a comparison, not the first
+fn nested_if_second_operand(a: i32) {
+    if a == 1 && if a == 13 { 10 } else { 0 } > 5 {
+        println!("nested if, second operand");
+    }
+    if 5 < if a == 13 { 10 } else { 0 } {
+        println!("nested if, right-hand side");
+    }
+}
+
+// block in an `if` condition, whole condition, with statements
+fn block_condition(a: i32) {
+    if {
+        let b = a + 1;
+        b > 5
+    } {
+        println!("block");
+    }
+}
+
+// block in an `if` condition that's just a tail expr, can be simplified
+fn simplifiable_block(a: i32) {
+    if { a > 5 } {
+        println!("simplifiable block");
+    }
+}
+
+// `match` in an `if` condition
+fn match_condition(a: i32) {
+    if match a {
+        0 => true,
+        _ => false,
+    } {
+        println!("match");
+    }
+}
+
+// `while` with a nested `if`; not hoistable, so only a help message is shown
+fn while_nested_if(a: i32) {
+    while if a == 13 { 10 } else { 0 } > 5 {
+        break;
+    }
+}
+
+// `while let` with a nested `if`; not hoistable either
+fn while_let_nested_if(a: i32) {
+    while let Some(v) = if a == 13 { Some(10) } else { None } {
+        break;
+    }
+}
+
+// `match` guard with a nested `if`; not hoistable, guards may reference the arm's bindings
+fn match_guard_nested_if(a: i32) {
+    match a {
+        x if if x == 13 { 10 } else { 0 } > 5 => {},
+        _ => {},
+    }
+}
+
+// closure with a multi-statement block body, passed as a call argument; help-only
+fn closure_multi_stmt(a: i32) {
+    if takes_closure(|| {
+        let b = a + 1;
+        b > 5
+    }) {
+        println!("closure");
+    }
+}
+
+// closure whose block body is just a tail expr, passed as a call argument; braces removable
+fn closure_simplifiable() {
+    if takes_closure_arg(|x| { x == 47 }) {
+        println!("simplifiable closure");
+    }
+}
+
+// not flagged by default: `lint-ifs-in-test-code` is `false` unless overridden by clippy.toml
+#[test]
+fn not_flagged_in_test_fn() {
+    let a = 10;
+    if { a > 5 } {
+        println!("not flagged, this is test code");
+    }
+}
+
+fn main() {
+    nested_if(10);
+    nested_if_else_if(10);
+    nested_if_second_operand(10);
+    block_condition(10);
+    simplifiable_block(10);
+    match_condition(10);
+    while_nested_if(10);
+    while_let_nested_if(10);
+    match_guard_nested_if(10);
+    closure_multi_stmt(10);
+    closure_simplifiable();
+}